@@ -0,0 +1,152 @@
+//! Event statistics and aggregation over `NgLog` data.
+//!
+//! This mirrors the kind of analysis ngStats and ngWorldStats performed on
+//! captured gameplay logs: frequency counts, activity over time, and the
+//! lifespan of individual event keys.
+
+use std::collections::BTreeMap;
+
+use NgLog;
+
+/// The first-seen/last-seen timestamps of a single event key, as computed by
+/// `activity_windows`.
+pub struct ActivityWindow {
+	/// The timestamp, in seconds, at which this key was first observed.
+	pub first_seen: f64,
+	/// The timestamp, in seconds, at which this key was last observed.
+	pub last_seen:  f64,
+}
+
+impl ActivityWindow {
+	/// The span of time, in seconds, between this key's first and last
+	/// occurrence.
+	pub fn duration(&self) -> f64 {
+		self.last_seen - self.first_seen
+	}
+}
+
+/// Counts how many times each distinct `event_id` occurs in `log`.
+pub fn count_by_event_id(log: &NgLog) -> BTreeMap<String, usize> {
+	let mut counts: BTreeMap<String, usize> = BTreeMap::new();
+	for event in &log.events {
+		*counts.entry(event.event_id.clone()).or_insert(0) += 1;
+	}
+	counts
+}
+
+/// Counts how many times each distinct `event_class` occurs in `log`.
+/// Events with no class are counted together under `None`.
+pub fn count_by_event_class(log: &NgLog) -> BTreeMap<Option<String>, usize> {
+	let mut counts: BTreeMap<Option<String>, usize> = BTreeMap::new();
+	for event in &log.events {
+		*counts.entry(event.event_class.clone()).or_insert(0) += 1;
+	}
+	counts
+}
+
+/// Counts events per fixed-width time bucket of `bucket_secs` seconds,
+/// keyed by bucket index (bucket `0` covers `[0, bucket_secs)`, bucket `1`
+/// covers `[bucket_secs, bucket_secs * 2)`, and so on).
+///
+/// Events whose `timestamp` does not parse as a floating-point number are
+/// skipped.
+pub fn count_by_time_bucket(log: &NgLog, bucket_secs: f64) -> BTreeMap<u64, usize> {
+	let mut counts: BTreeMap<u64, usize> = BTreeMap::new();
+	for event in &log.events {
+		if let Ok(t) = event.timestamp_secs() {
+			let bucket = (t / bucket_secs) as u64;
+			*counts.entry(bucket).or_insert(0) += 1;
+		}
+	}
+	counts
+}
+
+/// Computes the first-seen/last-seen timestamp of each distinct `event_id`
+/// in a single scan over `log`.
+///
+/// Events whose `timestamp` does not parse as a floating-point number are
+/// skipped.
+pub fn activity_windows(log: &NgLog) -> BTreeMap<String, ActivityWindow> {
+	let mut windows: BTreeMap<String, ActivityWindow> = BTreeMap::new();
+	for event in &log.events {
+		let t = match event.timestamp_secs() {
+			Ok(t) => t,
+			Err(_) => continue,
+		};
+		match windows.get_mut(&event.event_id) {
+			Some(w) => {
+				if t < w.first_seen {
+					w.first_seen = t;
+				}
+				if t > w.last_seen {
+					w.last_seen = t;
+				}
+			},
+			None => {
+				windows.insert(event.event_id.clone(), ActivityWindow {
+					first_seen: t,
+					last_seen:  t,
+				});
+			},
+		}
+	}
+	windows
+}
+
+#[cfg(test)]
+mod tests {
+	use NgLog;
+	use analysis::activity_windows;
+	use analysis::count_by_event_class;
+	use analysis::count_by_event_id;
+	use analysis::count_by_time_bucket;
+
+	fn sample_log() -> NgLog {
+		NgLog::from_string(&String::from(
+			"0.0\tcombat\tkill\tplayer1\n\
+			 10.0\tcombat\tkill\tplayer2\n\
+			 120.5\tchat\tsay\thello\n\
+			 200.0\tjoin\n\
+			 not-a-number\tjoin\n"
+		)).unwrap()
+	}
+
+	#[test]
+	fn count_by_event_id_counts_each_distinct_id() {
+		let counts = count_by_event_id(&sample_log());
+		assert_eq!(counts.get("kill"), Some(&2));
+		assert_eq!(counts.get("say"), Some(&1));
+		assert_eq!(counts.get("join"), Some(&2));
+		assert_eq!(counts.len(), 3);
+	}
+
+	#[test]
+	fn count_by_event_class_groups_classless_events_under_none() {
+		let counts = count_by_event_class(&sample_log());
+		assert_eq!(counts.get(&Some(String::from("combat"))), Some(&2));
+		assert_eq!(counts.get(&Some(String::from("chat"))), Some(&1));
+		assert_eq!(counts.get(&None), Some(&2));
+		assert_eq!(counts.len(), 3);
+	}
+
+	#[test]
+	fn count_by_time_bucket_skips_unparseable_timestamps() {
+		let counts = count_by_time_bucket(&sample_log(), 60.0);
+		assert_eq!(counts.get(&0), Some(&2));
+		assert_eq!(counts.get(&2), Some(&1));
+		assert_eq!(counts.get(&3), Some(&1));
+		assert_eq!(counts.values().sum::<usize>(), 4);
+	}
+
+	#[test]
+	fn activity_windows_tracks_first_and_last_occurrence_per_id() {
+		let windows = activity_windows(&sample_log());
+		let kill = windows.get("kill").unwrap();
+		assert_eq!(kill.first_seen, 0.0);
+		assert_eq!(kill.last_seen, 10.0);
+		assert_eq!(kill.duration(), 10.0);
+		let join = windows.get("join").unwrap();
+		assert_eq!(join.first_seen, 200.0);
+		assert_eq!(join.last_seen, 200.0);
+	}
+}
@@ -0,0 +1,117 @@
+//! Resolving gameplay-relative timestamps to absolute wall-clock time.
+
+use chrono::DateTime;
+use chrono::Duration;
+use chrono::FixedOffset;
+use chrono::Utc;
+
+use NgEvent;
+
+/// Resolves an `NgEvent`'s gameplay-relative `timestamp` to an absolute
+/// wall-clock time, given when the recording gameplay session began.
+///
+/// ngLog timestamps are seconds elapsed since gameplay began, which makes
+/// correlating events across sessions difficult on their own. A `Context`
+/// carries the session's start instant (and, optionally, a timezone offset
+/// to apply when rendering it), letting events from different players be
+/// placed on one shared timeline.
+pub struct Context {
+	/// The wall-clock instant at which gameplay began, if known.
+	pub start:  Option<DateTime<Utc>>,
+	/// The timezone offset to apply when rendering absolute timestamps.
+	pub offset: FixedOffset,
+}
+
+impl Context {
+	/// Constructs a `Context` with no known start time and no UTC offset.
+	pub fn new() -> Context {
+		Context {
+			start:  None,
+			offset: FixedOffset::east_opt(0).expect("zero offset is always valid"),
+		}
+	}
+
+	/// Returns this `Context` with `start` set to `start`.
+	pub fn with_start(mut self, start: DateTime<Utc>) -> Context {
+		self.start = Some(start);
+		self
+	}
+
+	/// Returns this `Context` with `offset` set to `offset`.
+	pub fn with_offset(mut self, offset: FixedOffset) -> Context {
+		self.offset = offset;
+		self
+	}
+
+	/// Resolves `event`'s `timestamp` to an absolute wall-clock time, or
+	/// `None` if this `Context` has no `start`, the timestamp does not
+	/// parse as an `f64`, or applying it to `start` would fall outside the
+	/// range of dates `DateTime` can represent.
+	pub fn resolve(&self, event: &NgEvent) -> Option<DateTime<FixedOffset>> {
+		let start = match self.start {
+			Some(start) => start,
+			None => return None,
+		};
+		let secs = match event.timestamp_secs() {
+			Ok(secs) => secs,
+			Err(_) => return None,
+		};
+		let elapsed = match Duration::try_milliseconds((secs * 1000.0).round() as i64) {
+			Some(elapsed) => elapsed,
+			None => return None,
+		};
+		start.checked_add_signed(elapsed).map(|dt| dt.with_timezone(&self.offset))
+	}
+
+	/// Returns an iterator yielding each event in `events` alongside its
+	/// resolved absolute wall-clock time (`None` if it could not be
+	/// resolved).
+	pub fn resolve_all<'a, I>(&'a self, events: I) -> ResolvedTimes<'a, I::IntoIter> where
+	I: IntoIterator<Item = &'a NgEvent> {
+		ResolvedTimes {
+			context: self,
+			events:  events.into_iter(),
+		}
+	}
+}
+
+/// An iterator yielding `(Option<DateTime<FixedOffset>>, &NgEvent)` pairs,
+/// produced by `Context::resolve_all`.
+pub struct ResolvedTimes<'a, I> {
+	context: &'a Context,
+	events:  I,
+}
+
+impl<'a, I> Iterator for ResolvedTimes<'a, I> where
+I: Iterator<Item = &'a NgEvent> {
+	type Item = (Option<DateTime<FixedOffset>>, &'a NgEvent);
+
+	fn next(&mut self) -> Option<Self::Item> {
+		self.events.next().map(|event| (self.context.resolve(event), event))
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use chrono::Utc;
+
+	use NgEvent;
+	use context::Context;
+
+	#[test]
+	fn resolve_returns_none_instead_of_panicking_on_out_of_range_timestamp() {
+		let event = NgEvent::new(String::from("-1e20"), None, String::from("kill"), Vec::new());
+		let context = Context::new().with_start(Utc::now());
+		assert_eq!(context.resolve(&event), None);
+	}
+
+	#[test]
+	fn resolve_returns_none_instead_of_panicking_when_start_plus_elapsed_overflows() {
+		// A parseable `f64`, and small enough for `Duration::try_milliseconds`
+		// to succeed, but still large enough that adding it to `start` falls
+		// outside the range `DateTime` can represent.
+		let event = NgEvent::new(String::from("9000000000000.0"), None, String::from("kill"), Vec::new());
+		let context = Context::new().with_start(Utc::now());
+		assert_eq!(context.resolve(&event), None);
+	}
+}
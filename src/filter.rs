@@ -0,0 +1,275 @@
+//! Inclusion/exclusion filtering over `NgEvent`s.
+
+use std::collections::HashSet;
+use std::io::Result as IoResult;
+
+use regex::Regex;
+
+use NgEvent;
+
+/// A set of inclusion/exclusion criteria for selecting `NgEvent`s.
+///
+/// Criteria combine with AND semantics: an event must satisfy every
+/// criterion that has been set to be selected. Use `NgLog::filter` to apply
+/// a `Filter` to a whole log, or `FilterIterator::filter_events` to apply
+/// one while streaming from an `NgEventReader`.
+pub struct Filter {
+	include_ids:     Option<HashSet<String>>,
+	exclude_ids:     HashSet<String>,
+	include_classes: Option<HashSet<String>>,
+	exclude_classes: HashSet<String>,
+	id_regex:        Option<Regex>,
+	param_regex:     Option<Regex>,
+	window:          Option<(f64, f64)>,
+}
+
+impl Filter {
+	/// Constructs an empty `Filter` that selects every event.
+	pub fn new() -> Filter {
+		Filter {
+			include_ids:     None,
+			exclude_ids:     HashSet::new(),
+			include_classes: None,
+			exclude_classes: HashSet::new(),
+			id_regex:        None,
+			param_regex:     None,
+			window:          None,
+		}
+	}
+
+	/// Restricts selection to events whose `event_id` is in `ids`. Calling
+	/// this more than once replaces the previous set.
+	pub fn include_event_ids<I>(mut self, ids: I) -> Filter where
+	I: IntoIterator<Item = String> {
+		self.include_ids = Some(ids.into_iter().collect());
+		self
+	}
+
+	/// Excludes events whose `event_id` is in `ids`.
+	pub fn exclude_event_ids<I>(mut self, ids: I) -> Filter where
+	I: IntoIterator<Item = String> {
+		self.exclude_ids.extend(ids);
+		self
+	}
+
+	/// Restricts selection to events whose `event_class` is in `classes`.
+	/// Calling this more than once replaces the previous set.
+	pub fn include_event_classes<I>(mut self, classes: I) -> Filter where
+	I: IntoIterator<Item = String> {
+		self.include_classes = Some(classes.into_iter().collect());
+		self
+	}
+
+	/// Excludes events whose `event_class` is in `classes`.
+	pub fn exclude_event_classes<I>(mut self, classes: I) -> Filter where
+	I: IntoIterator<Item = String> {
+		self.exclude_classes.extend(classes);
+		self
+	}
+
+	/// Restricts selection to events whose `event_id` matches `regex`.
+	pub fn matching_event_id(mut self, regex: Regex) -> Filter {
+		self.id_regex = Some(regex);
+		self
+	}
+
+	/// Restricts selection to events with at least one parameter matching
+	/// `regex`.
+	pub fn matching_param(mut self, regex: Regex) -> Filter {
+		self.param_regex = Some(regex);
+		self
+	}
+
+	/// Restricts selection to events whose `timestamp`, parsed as an `f64`,
+	/// falls in `[min, max)`. Events whose `timestamp` does not parse are
+	/// excluded.
+	pub fn time_window(mut self, min: f64, max: f64) -> Filter {
+		self.window = Some((min, max));
+		self
+	}
+
+	/// Reports whether `event` satisfies every criterion set on this
+	/// `Filter`.
+	pub fn matches(&self, event: &NgEvent) -> bool {
+		if let Some(ref ids) = self.include_ids {
+			if !ids.contains(&event.event_id) {
+				return false;
+			}
+		}
+		if self.exclude_ids.contains(&event.event_id) {
+			return false;
+		}
+		if let Some(ref classes) = self.include_classes {
+			match event.event_class {
+				Some(ref class) if classes.contains(class) => {},
+				_ => return false,
+			}
+		}
+		if let Some(ref class) = event.event_class {
+			if self.exclude_classes.contains(class) {
+				return false;
+			}
+		}
+		if let Some(ref regex) = self.id_regex {
+			if !regex.is_match(&event.event_id) {
+				return false;
+			}
+		}
+		if let Some(ref regex) = self.param_regex {
+			if !event.event_params.iter().any(|p| regex.is_match(p)) {
+				return false;
+			}
+		}
+		if let Some((min, max)) = self.window {
+			match event.timestamp_secs() {
+				Ok(t) if t >= min && t < max => {},
+				_ => return false,
+			}
+		}
+		true
+	}
+}
+
+/// An iterator adapter that yields only the items of an inner
+/// `Iterator<Item = IoResult<NgEvent>>` that satisfy a `Filter`. Errors from
+/// the inner iterator pass through unchanged. Constructed via
+/// `FilterIterator::filter_events`.
+pub struct Filtered<I> {
+	inner:  I,
+	filter: Filter,
+}
+
+impl<I> Iterator for Filtered<I> where
+I: Iterator<Item = IoResult<NgEvent>> {
+	type Item = IoResult<NgEvent>;
+
+	fn next(&mut self) -> Option<IoResult<NgEvent>> {
+		loop {
+			match self.inner.next() {
+				Some(Ok(event)) => if self.filter.matches(&event) {
+					return Some(Ok(event));
+				},
+				other => return other,
+			}
+		}
+	}
+}
+
+/// Extends iterators of `IoResult<NgEvent>` (such as `NgEventReader`) with a
+/// `filter_events` adapter.
+pub trait FilterIterator: Iterator<Item = IoResult<NgEvent>> + Sized {
+	/// Returns an iterator yielding only the events that satisfy `filter`.
+	fn filter_events(self, filter: Filter) -> Filtered<Self> {
+		Filtered {
+			inner:  self,
+			filter: filter,
+		}
+	}
+}
+
+impl<I> FilterIterator for I where
+I: Iterator<Item = IoResult<NgEvent>> {}
+
+#[cfg(test)]
+mod tests {
+	use std::io::Error as IoError;
+	use std::io::ErrorKind as IoErrorKind;
+
+	use regex::Regex;
+
+	use NgEvent;
+	use filter::Filter;
+	use filter::FilterIterator;
+
+	fn event(timestamp: &str, class: Option<&str>, id: &str, params: Vec<&str>) -> NgEvent {
+		NgEvent::new(
+			String::from(timestamp),
+			class.map(String::from),
+			String::from(id),
+			params.into_iter().map(String::from).collect()
+		)
+	}
+
+	#[test]
+	fn exclude_takes_precedence_over_include() {
+		let filter = Filter::new()
+			.include_event_ids(vec![String::from("kill"), String::from("heal")])
+			.exclude_event_ids(vec![String::from("heal")]);
+		assert!(filter.matches(&event("0.0", None, "kill", vec![])));
+		assert!(!filter.matches(&event("0.0", None, "heal", vec![])));
+		assert!(!filter.matches(&event("0.0", None, "say", vec![])));
+	}
+
+	#[test]
+	fn events_without_a_class_are_excluded_by_include_event_classes() {
+		let filter = Filter::new().include_event_classes(vec![String::from("combat")]);
+		assert!(filter.matches(&event("0.0", Some("combat"), "kill", vec![])));
+		assert!(!filter.matches(&event("0.0", None, "kill", vec![])));
+	}
+
+	#[test]
+	fn events_without_a_class_are_unaffected_by_exclude_event_classes() {
+		let filter = Filter::new().exclude_event_classes(vec![String::from("combat")]);
+		assert!(filter.matches(&event("0.0", None, "kill", vec![])));
+		assert!(!filter.matches(&event("0.0", Some("combat"), "kill", vec![])));
+	}
+
+	#[test]
+	fn matching_event_id_applies_a_regex() {
+		let filter = Filter::new().matching_event_id(Regex::new("^kill_").unwrap());
+		assert!(filter.matches(&event("0.0", None, "kill_player", vec![])));
+		assert!(!filter.matches(&event("0.0", None, "say", vec![])));
+	}
+
+	#[test]
+	fn matching_param_applies_a_regex_to_any_param() {
+		let filter = Filter::new().matching_param(Regex::new("^player2$").unwrap());
+		assert!(filter.matches(&event("0.0", None, "kill", vec!["player1", "player2"])));
+		assert!(!filter.matches(&event("0.0", None, "kill", vec!["player1", "player3"])));
+	}
+
+	#[test]
+	fn time_window_is_half_open() {
+		let filter = Filter::new().time_window(120.0, 300.0);
+		assert!(filter.matches(&event("120.0", None, "kill", vec![])));
+		assert!(filter.matches(&event("299.999", None, "kill", vec![])));
+		assert!(!filter.matches(&event("300.0", None, "kill", vec![])));
+		assert!(!filter.matches(&event("119.999", None, "kill", vec![])));
+	}
+
+	#[test]
+	fn time_window_excludes_unparseable_timestamps() {
+		let filter = Filter::new().time_window(120.0, 300.0);
+		assert!(!filter.matches(&event("not-a-number", None, "kill", vec![])));
+	}
+
+	#[test]
+	fn filter_events_extracts_kills_within_a_time_window() {
+		let events = vec![
+			Ok(event("0.0", None, "kill", vec![])),
+			Ok(event("150.0", None, "kill", vec![])),
+			Ok(event("150.0", None, "say", vec![])),
+			Ok(event("301.0", None, "kill", vec![])),
+		];
+		let filter = Filter::new()
+			.include_event_ids(vec![String::from("kill")])
+			.time_window(120.0, 300.0);
+		let matched: Vec<NgEvent> = events.into_iter().filter_events(filter)
+			.map(|r| r.unwrap())
+			.collect();
+		assert_eq!(matched.len(), 1);
+		assert_eq!(matched[0].timestamp, "150.0");
+	}
+
+	#[test]
+	fn filter_events_passes_errors_through_unchanged() {
+		let events = vec![
+			Ok(event("0.0", None, "kill", vec![])),
+			Err(IoError::new(IoErrorKind::InvalidData, "bad line")),
+		];
+		let filter = Filter::new().include_event_ids(vec![String::from("say")]);
+		let results: Vec<_> = events.into_iter().filter_events(filter).collect();
+		assert_eq!(results.len(), 1);
+		assert!(results[0].is_err());
+	}
+}
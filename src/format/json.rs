@@ -0,0 +1,54 @@
+//! A structured JSON encoding of `NgLog` data.
+
+use std::io::Error as IoError;
+use std::io::ErrorKind as IoErrorKind;
+use std::io::Read;
+use std::io::Write;
+use std::io::Result as IoResult;
+
+use serde_json;
+
+use context::Context;
+use NgLog;
+use format::Format;
+use format::WireEvent;
+
+/// A JSON encoding of `NgLog` data: an array of objects, one per event, each
+/// carrying `timestamp`, `class`, `id`, and `params` fields.
+///
+/// This trades the native form's compactness for interoperability with
+/// modern log analysis pipelines that expect JSON input.
+pub struct JsonFormat;
+
+impl Format for JsonFormat {
+	fn read(&self, r: &mut dyn Read) -> IoResult<NgLog> {
+		let events: Vec<WireEvent> = try!(serde_json::from_reader(r).map_err(|e|
+			IoError::new(IoErrorKind::InvalidData, format!("{}", e))
+		));
+		let mut log = NgLog::new(events.len());
+		for event in events {
+			log.events.push(event.into());
+		}
+		Ok(log)
+	}
+
+	fn write(&self, log: &NgLog, w: &mut dyn Write) -> IoResult<()> {
+		let events: Vec<WireEvent> = log.events.iter().map(WireEvent::from).collect();
+		serde_json::to_writer(w, &events).map_err(|e|
+			IoError::new(IoErrorKind::InvalidData, format!("{}", e))
+		)
+	}
+}
+
+impl JsonFormat {
+	/// Like `Format::write`, but resolves each event's wall-clock time via
+	/// `context` and includes it as an ISO-8601 `absolute_time` field.
+	pub fn write_with_context(&self, log: &NgLog, context: &Context, w: &mut dyn Write) -> IoResult<()> {
+		let events: Vec<WireEvent> = log.events.iter()
+			.map(|event| WireEvent::with_context(event, context))
+			.collect();
+		serde_json::to_writer(w, &events).map_err(|e|
+			IoError::new(IoErrorKind::InvalidData, format!("{}", e))
+		)
+	}
+}
@@ -0,0 +1,124 @@
+//! Pluggable `NgLog` encodings.
+//!
+//! Besides its native tab-separated textual form, `NgLog` data can be
+//! expressed in other encodings better suited to modern analysis pipelines.
+//! The `Format` trait abstracts over these encodings so callers can convert
+//! a captured gameplay log between them without depending on any one of them
+//! directly.
+
+use std::io::Read;
+use std::io::Write;
+use std::io::Result as IoResult;
+
+use context::Context;
+use NgEvent;
+use NgLog;
+
+pub mod native;
+pub mod json;
+pub mod msgpack;
+
+pub use self::json::JsonFormat;
+pub use self::msgpack::MsgPackFormat;
+pub use self::native::NativeFormat;
+
+/// A reversible encoding between `NgLog` data and a byte stream.
+///
+/// Implementations translate an `NgLog` to and from some on-disk or
+/// on-wire representation, letting `NgLog` readers and writers be expressed
+/// as one `Format` impl among several.
+pub trait Format {
+	/// Reads an `NgLog` from the given reader using this format's encoding.
+	///
+	/// # Failures
+	///
+	/// If the input data is malformed, this method returns an
+	/// `std::io::Error` instance describing the error.
+	fn read(&self, r: &mut dyn Read) -> IoResult<NgLog>;
+
+	/// Writes an `NgLog` to the given writer using this format's encoding.
+	fn write(&self, log: &NgLog, w: &mut dyn Write) -> IoResult<()>;
+}
+
+/// A serializable view of an `NgEvent`, shared by the `json` and `msgpack`
+/// backends so they agree on field names and need not duplicate the
+/// conversion to and from `NgEvent`.
+#[derive(Serialize, Deserialize)]
+struct WireEvent {
+	timestamp:     String,
+	#[serde(skip_serializing_if = "Option::is_none", default)]
+	class:         Option<String>,
+	id:            String,
+	#[serde(default)]
+	params:        Vec<String>,
+	/// The event's timestamp resolved to an ISO-8601 wall-clock time via a
+	/// `Context`, if one was supplied when writing. Absent on plain reads
+	/// and writes.
+	#[serde(skip_serializing_if = "Option::is_none", default)]
+	absolute_time: Option<String>,
+}
+
+impl<'a> From<&'a NgEvent> for WireEvent {
+	fn from(event: &'a NgEvent) -> WireEvent {
+		WireEvent {
+			timestamp:     event.timestamp.clone(),
+			class:         event.event_class.clone(),
+			id:            event.event_id.clone(),
+			params:        event.event_params.clone(),
+			absolute_time: None,
+		}
+	}
+}
+
+impl From<WireEvent> for NgEvent {
+	fn from(event: WireEvent) -> NgEvent {
+		NgEvent::new(event.timestamp, event.class, event.id, event.params)
+	}
+}
+
+impl WireEvent {
+	/// Builds a `WireEvent` with `absolute_time` resolved via `context`.
+	fn with_context(event: &NgEvent, context: &Context) -> WireEvent {
+		let mut wire = WireEvent::from(event);
+		wire.absolute_time = context.resolve(event).map(|t| t.to_rfc3339());
+		wire
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use NgLog;
+	use format::Format;
+	use format::JsonFormat;
+	use format::MsgPackFormat;
+	use format::NativeFormat;
+
+	fn sample_log() -> NgLog {
+		NgLog::from_string(&String::from(
+			"0.0\tkill\tplayer1\tplayer2\n120.5\tsay\tplayer2\thello\n300.0\tjoin\n"
+		)).unwrap()
+	}
+
+	fn assert_round_trips<F: Format>(format: F) {
+		let log = sample_log();
+		let mut encoded: Vec<u8> = Vec::new();
+		format.write(&log, &mut encoded).unwrap();
+		let decoded = format.read(&mut &encoded[..]).unwrap();
+		assert_eq!(log.to_string(), decoded.to_string());
+	}
+
+	#[test]
+	fn native_format_round_trips() {
+		assert_round_trips(NativeFormat);
+	}
+
+	#[test]
+	fn json_format_round_trips() {
+		assert_round_trips(JsonFormat);
+	}
+
+	#[test]
+	fn msgpack_format_round_trips() {
+		assert_round_trips(MsgPackFormat);
+	}
+}
@@ -0,0 +1,55 @@
+//! A compact MessagePack encoding of `NgLog` data.
+
+use std::io::Error as IoError;
+use std::io::ErrorKind as IoErrorKind;
+use std::io::Read;
+use std::io::Write;
+use std::io::Result as IoResult;
+
+use rmp_serde;
+use serde::Serialize;
+
+use context::Context;
+use NgLog;
+use format::Format;
+use format::WireEvent;
+
+/// A MessagePack encoding of `NgLog` data, using the same per-event shape as
+/// `JsonFormat` but serialized as compact binary records.
+///
+/// This suits callers who need the structure of the JSON form without its
+/// textual overhead, e.g. when shipping large logs between processes.
+pub struct MsgPackFormat;
+
+impl Format for MsgPackFormat {
+	fn read(&self, r: &mut dyn Read) -> IoResult<NgLog> {
+		let events: Vec<WireEvent> = try!(rmp_serde::from_read(r).map_err(|e|
+			IoError::new(IoErrorKind::InvalidData, format!("{}", e))
+		));
+		let mut log = NgLog::new(events.len());
+		for event in events {
+			log.events.push(event.into());
+		}
+		Ok(log)
+	}
+
+	fn write(&self, log: &NgLog, w: &mut dyn Write) -> IoResult<()> {
+		let events: Vec<WireEvent> = log.events.iter().map(WireEvent::from).collect();
+		events.serialize(&mut rmp_serde::Serializer::new(w).with_struct_map()).map_err(|e|
+			IoError::new(IoErrorKind::InvalidData, format!("{}", e))
+		)
+	}
+}
+
+impl MsgPackFormat {
+	/// Like `Format::write`, but resolves each event's wall-clock time via
+	/// `context` and includes it as an ISO-8601 `absolute_time` field.
+	pub fn write_with_context(&self, log: &NgLog, context: &Context, w: &mut dyn Write) -> IoResult<()> {
+		let events: Vec<WireEvent> = log.events.iter()
+			.map(|event| WireEvent::with_context(event, context))
+			.collect();
+		events.serialize(&mut rmp_serde::Serializer::new(w).with_struct_map()).map_err(|e|
+			IoError::new(IoErrorKind::InvalidData, format!("{}", e))
+		)
+	}
+}
@@ -0,0 +1,22 @@
+//! The crate's native tab-separated textual form.
+
+use std::io::Read;
+use std::io::Write;
+use std::io::Result as IoResult;
+
+use NgLog;
+use format::Format;
+
+/// The native ngLog textual form, as produced by `NgLog::to_string` and
+/// consumed by `NgLog::local_from_reader`.
+pub struct NativeFormat;
+
+impl Format for NativeFormat {
+	fn read(&self, r: &mut dyn Read) -> IoResult<NgLog> {
+		NgLog::local_from_reader(r)
+	}
+
+	fn write(&self, log: &NgLog, w: &mut dyn Write) -> IoResult<()> {
+		w.write_all(log.to_string().as_bytes())
+	}
+}
@@ -57,12 +57,40 @@
 //! println!("{}", log.to_string());
 //! ```
 
+extern crate chrono;
+extern crate regex;
+extern crate rmp_serde;
+extern crate serde;
+#[macro_use]
+extern crate serde_derive;
+extern crate serde_json;
+
+pub mod analysis;
+pub mod context;
+pub mod filter;
+pub mod format;
+pub mod reader;
+
+pub use context::Context;
+pub use filter::Filter;
+pub use filter::FilterIterator;
+pub use reader::NgEventReader;
+
 use std::io::Error as IoError;
 use std::io::ErrorKind as IoErrorKind;
 use std::io::Result as IoResult;
 use std::io::Read;
+use std::io::Write;
 use std::string::ToString;
 
+/// The key byte used by `to_world_bytes` and `world_to_writer` when no
+/// explicit key is supplied.
+///
+/// Since `world_from_reader` decodes each byte as `v[0] ^ v[1]`, any key
+/// value produces a valid round trip; this constant exists purely so callers
+/// who do not care about the key still get deterministic output.
+pub const DEFAULT_WORLD_KEY: u8 = 0;
+
 /// A type representing an ngLog-formatted file.
 pub struct NgLog {
 	/// A collection of ngLog events.
@@ -70,6 +98,7 @@ pub struct NgLog {
 }
 
 /// A type representing an ngLog event.
+#[derive(Clone)]
 pub struct NgEvent {
 	/// A floating-point value representing the elapsed time since gameplay began.
 	pub timestamp:    String,
@@ -98,12 +127,12 @@ impl NgLog {
 	/// If the input data is either not valid UTF-8 or malformed, this method
 	/// returns an `std::io::Error` instance describing the error.
 	pub fn local_from_reader<T>(reader: &mut T) -> IoResult<NgLog> where
-	T: Read {
-		let mut data: Vec<u8> = Vec::with_capacity(0);
-		try!(reader.read_to_end(&mut data));
-		NgLog::from_string(&try!(String::from_utf8(data).map_err(|e|
-			IoError::new(IoErrorKind::InvalidData, format!("{}", e))
-		)))
+	T: Read + ?Sized {
+		let mut log = NgLog::new(0);
+		for event in NgEventReader::new(reader) {
+			log.events.push(try!(event));
+		}
+		Ok(log)
 	}
 
 	/// Constructs a new `NgLog` instance using data from a type implementing
@@ -145,6 +174,89 @@ impl NgLog {
 		}
 		Ok(log)
 	}
+
+	/// Returns a new `NgLog` containing only the events of this log that
+	/// satisfy `filter`.
+	pub fn filter(&self, filter: &filter::Filter) -> NgLog {
+		let mut log = NgLog::new(0);
+		for event in &self.events {
+			if filter.matches(event) {
+				log.events.push(event.clone());
+			}
+		}
+		log
+	}
+
+	/// Encodes this `NgLog` into the world-server form and writes it to the
+	/// given type implementing `std::io::Write`, using `DEFAULT_WORLD_KEY` as
+	/// the key byte.
+	///
+	/// This is the inverse of `world_from_reader`: for every output byte `b`,
+	/// a pair `(k, k ^ b)` is written, so
+	/// `NgLog::world_from_reader(&mut &log.to_world_bytes()[..])` reproduces
+	/// `log`.
+	///
+	/// # Examples
+	///
+	/// ```rust
+	/// use ng_log::NgLog;
+	///
+	/// let log = NgLog::from_string(&String::from("0.0\tkill\tplayer1\n")).unwrap();
+	/// let mut encoded: Vec<u8> = Vec::new();
+	/// log.world_to_writer(&mut encoded).unwrap();
+	/// let decoded = NgLog::world_from_reader(&mut &encoded[..]).unwrap();
+	/// assert_eq!(log.to_string(), decoded.to_string());
+	/// ```
+	pub fn world_to_writer<W>(&self, w: &mut W) -> IoResult<()> where
+	W: Write {
+		self.world_to_writer_with_key(w, DEFAULT_WORLD_KEY)
+	}
+
+	/// Like `world_to_writer`, but uses the given fixed key byte instead of
+	/// `DEFAULT_WORLD_KEY`.
+	pub fn world_to_writer_with_key<W>(&self, w: &mut W, key: u8) -> IoResult<()> where
+	W: Write {
+		self.world_to_writer_with(w, move || key)
+	}
+
+	/// Like `world_to_writer`, but calls the given closure to obtain the key
+	/// byte for each output byte, allowing a caller-supplied running key
+	/// instead of a single fixed value.
+	pub fn world_to_writer_with<W, F>(&self, w: &mut W, mut next_key: F) -> IoResult<()> where
+	W: Write, F: FnMut() -> u8 {
+		for b in self.to_string().into_bytes() {
+			let k = next_key();
+			try!(w.write_all(&[k, k ^ b]));
+		}
+		Ok(())
+	}
+
+	/// Encodes this `NgLog` into the world-server form, returning the result
+	/// as a byte vector. See `world_to_writer` for details.
+	pub fn to_world_bytes(&self) -> Vec<u8> {
+		self.to_world_bytes_with_key(DEFAULT_WORLD_KEY)
+	}
+
+	/// Like `to_world_bytes`, but uses the given fixed key byte instead of
+	/// `DEFAULT_WORLD_KEY`.
+	pub fn to_world_bytes_with_key(&self, key: u8) -> Vec<u8> {
+		self.to_world_bytes_with(move || key)
+	}
+
+	/// Like `to_world_bytes`, but calls the given closure to obtain the key
+	/// byte for each output byte, allowing a caller-supplied running key
+	/// instead of a single fixed value.
+	pub fn to_world_bytes_with<F>(&self, mut next_key: F) -> Vec<u8> where
+	F: FnMut() -> u8 {
+		let s = self.to_string();
+		let mut out: Vec<u8> = Vec::with_capacity(s.len() * 2);
+		for b in s.into_bytes() {
+			let k = next_key();
+			out.push(k);
+			out.push(k ^ b);
+		}
+		out
+	}
 }
 
 impl ToString for NgLog {
@@ -194,6 +306,66 @@ impl NgEvent {
 			))
 		}
 	}
+
+	/// Like `from_string`, but additionally validates that the timestamp
+	/// column parses as an `f64`, rejecting malformed lines up front rather
+	/// than silently carrying a timestamp that can never be parsed later.
+	///
+	/// # Failures
+	///
+	/// If the given data is malformed, or its timestamp does not parse as
+	/// an `f64`, this method returns an `std::io::Error` instance
+	/// describing the error.
+	pub fn from_string_strict(s: &String) -> IoResult<NgEvent> {
+		let event = try!(NgEvent::from_string(s));
+		try!(event.timestamp_secs());
+		Ok(event)
+	}
+
+	/// Parses this event's `timestamp` field as a floating-point number of
+	/// elapsed seconds.
+	///
+	/// # Failures
+	///
+	/// Returns an `std::io::Error` if `timestamp` does not parse as an
+	/// `f64`.
+	pub fn timestamp_secs(&self) -> IoResult<f64> {
+		self.timestamp.parse::<f64>().map_err(|e|
+			IoError::new(IoErrorKind::InvalidData, format!("{}", e))
+		)
+	}
+
+	/// Parses the parameter at `index` as an `f64`.
+	///
+	/// # Failures
+	///
+	/// Returns an `std::io::Error` if `index` is out of bounds or the
+	/// parameter does not parse as an `f64`.
+	pub fn param_f64(&self, index: usize) -> IoResult<f64> {
+		try!(self.param_str(index)).parse::<f64>().map_err(|e|
+			IoError::new(IoErrorKind::InvalidData, format!("{}", e))
+		)
+	}
+
+	/// Parses the parameter at `index` as an `i64`.
+	///
+	/// # Failures
+	///
+	/// Returns an `std::io::Error` if `index` is out of bounds or the
+	/// parameter does not parse as an `i64`.
+	pub fn param_i64(&self, index: usize) -> IoResult<i64> {
+		try!(self.param_str(index)).parse::<i64>().map_err(|e|
+			IoError::new(IoErrorKind::InvalidData, format!("{}", e))
+		)
+	}
+
+	/// Returns the parameter at `index`, or an `std::io::Error` if `index`
+	/// is out of bounds.
+	fn param_str(&self, index: usize) -> IoResult<&str> {
+		self.event_params.get(index).map(String::as_str).ok_or_else(||
+			IoError::new(IoErrorKind::InvalidData, "Parameter index out of bounds")
+		)
+	}
 }
 
 impl ToString for NgEvent {
@@ -210,3 +382,44 @@ impl ToString for NgEvent {
 		s
 	}
 }
+
+#[cfg(test)]
+mod tests {
+	use super::NgLog;
+
+	fn sample_log() -> NgLog {
+		NgLog::from_string(&String::from(
+			"0.0\tkill\tplayer1\tplayer2\n120.5\tsay\tplayer2\thello, \u{00e9}\u{00e9}\u{00e9}\n"
+		)).unwrap()
+	}
+
+	#[test]
+	fn world_round_trip_with_default_key() {
+		let log = sample_log();
+		let encoded = log.to_world_bytes();
+		let decoded = NgLog::world_from_reader(&mut &encoded[..]).unwrap();
+		assert_eq!(log.to_string(), decoded.to_string());
+	}
+
+	#[test]
+	fn world_round_trip_with_fixed_key() {
+		let log = sample_log();
+		let mut encoded: Vec<u8> = Vec::new();
+		log.world_to_writer_with_key(&mut encoded, 0x5a).unwrap();
+		let decoded = NgLog::world_from_reader(&mut &encoded[..]).unwrap();
+		assert_eq!(log.to_string(), decoded.to_string());
+	}
+
+	#[test]
+	fn world_round_trip_with_running_key() {
+		let log = sample_log();
+		let mut key: u8 = 1;
+		let encoded = log.to_world_bytes_with(|| {
+			let k = key;
+			key = key.wrapping_add(1);
+			k
+		});
+		let decoded = NgLog::world_from_reader(&mut &encoded[..]).unwrap();
+		assert_eq!(log.to_string(), decoded.to_string());
+	}
+}
@@ -0,0 +1,78 @@
+//! A streaming, line-by-line `NgEvent` parser.
+
+use std::io::BufRead;
+use std::io::BufReader;
+use std::io::Lines;
+use std::io::Read;
+use std::io::Result as IoResult;
+
+use NgEvent;
+
+/// An iterator over the events in an ngLog-formatted stream, parsed one
+/// line at a time.
+///
+/// Unlike `NgLog::local_from_reader`, which collects every parsed event into
+/// an `NgLog` before returning, `NgEventReader` yields events lazily as it is
+/// iterated, allowing
+/// constant-memory processing, early termination, and filtering without
+/// materializing the full `Vec<NgEvent>`.
+pub struct NgEventReader<R> where
+R: Read {
+	lines: Lines<BufReader<R>>,
+}
+
+impl<R> NgEventReader<R> where
+R: Read {
+	/// Wraps `reader` in a buffered, line-oriented `NgEvent` parser.
+	pub fn new(reader: R) -> NgEventReader<R> {
+		NgEventReader {
+			lines: BufReader::new(reader).lines(),
+		}
+	}
+}
+
+impl<R> Iterator for NgEventReader<R> where
+R: Read {
+	type Item = IoResult<NgEvent>;
+
+	fn next(&mut self) -> Option<IoResult<NgEvent>> {
+		match self.lines.next() {
+			Some(Ok(line)) => Some(NgEvent::from_string(&line)),
+			Some(Err(e))   => Some(Err(e)),
+			None           => None,
+		}
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use reader::NgEventReader;
+
+	#[test]
+	fn iterates_events_one_line_at_a_time() {
+		let data = b"0.0\tcombat\tkill\tplayer1\n120.5\tjoin\n" as &[u8];
+		let events: Vec<_> = NgEventReader::new(data).map(|r| r.unwrap()).collect();
+		assert_eq!(events.len(), 2);
+		assert_eq!(events[0].timestamp, "0.0");
+		assert_eq!(events[0].event_id, "kill");
+		assert_eq!(events[1].timestamp, "120.5");
+		assert_eq!(events[1].event_id, "join");
+	}
+
+	#[test]
+	fn yields_an_error_for_a_malformed_line_without_losing_earlier_events() {
+		let data = b"0.0\tcombat\tkill\tplayer1\nmalformed\n" as &[u8];
+		let mut reader = NgEventReader::new(data);
+		assert!(reader.next().unwrap().is_ok());
+		assert!(reader.next().unwrap().is_err());
+		assert!(reader.next().is_none());
+	}
+
+	#[test]
+	fn propagates_io_errors_from_invalid_utf8() {
+		let data = b"0.0\tkill\n\xff\xfe\n" as &[u8];
+		let mut reader = NgEventReader::new(data);
+		assert!(reader.next().unwrap().is_ok());
+		assert!(reader.next().unwrap().is_err());
+	}
+}